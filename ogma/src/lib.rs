@@ -105,6 +105,67 @@ struct HelpExample {
     code: &'static str,
 }
 
+/// Classification of a single [`HelpMessage`] parameter, for structured consumers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HelpParamKind {
+    /// A required parameter.
+    Required(String),
+    /// An optional parameter.
+    Optional(String),
+    /// A custom, free-form parameter description.
+    Custom(String),
+}
+
+/// A structured view over a [`HelpMessage`], returned by [`HelpMessage::to_structured`].
+///
+/// This is intended for editor/LSP integrations that want the raw fields (command,
+/// description, parameters, flags, examples) rather than the rendered [`Display`] text that
+/// [`help_as_error`] produces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HelpData {
+    /// The command name.
+    pub command: String,
+    /// The help description.
+    pub description: String,
+    /// Parameters, in declaration order (the `Break` formatting marker is not represented).
+    pub parameters: Vec<HelpParamKind>,
+    /// `(flag-name, description)` pairs.
+    pub flags: Vec<(String, String)>,
+    /// `(description, example-code)` pairs.
+    pub examples: Vec<(String, String)>,
+}
+
+impl HelpMessage {
+    /// Returns a structured view of this help message, for programmatic consumers rather than
+    /// the rendered [`Display`]/[`help_as_error`] text.
+    pub fn to_structured(&self) -> HelpData {
+        HelpData {
+            command: self.cmd.to_string(),
+            description: self.desc.to_string(),
+            parameters: self
+                .params
+                .iter()
+                .filter_map(|p| match p {
+                    HelpParameter::Required(s) => Some(HelpParamKind::Required(s.to_string())),
+                    HelpParameter::Optional(s) => Some(HelpParamKind::Optional(s.to_string())),
+                    HelpParameter::Custom(s) => Some(HelpParamKind::Custom(s.to_string())),
+                    HelpParameter::Break => None,
+                })
+                .collect(),
+            flags: self
+                .flags
+                .iter()
+                .map(|&(name, desc)| (name.to_string(), desc.to_string()))
+                .collect(),
+            examples: self
+                .examples
+                .iter()
+                .map(|e| (e.desc.to_string(), e.code.to_string()))
+                .collect(),
+        }
+    }
+}
+
 // ###### ERROR ################################################################
 /// Processing error.
 ///
@@ -133,6 +194,59 @@ struct ErrorTrace {
     len: usize,
 }
 
+impl Error {
+    /// Serialises the diagnostic as a structured JSON string.
+    ///
+    /// This exposes `cat`, `desc`, each [`ErrorTrace`] (`loc`, `source`, `desc`, `start`, `len`),
+    /// and `help_msg`, so editor/LSP integrations can highlight the exact `start..start+len`
+    /// span per trace without scraping the human-formatted, colourised text that
+    /// [`Error::print`]/[`print_error`] produce. `cat` and `loc` are rendered via their
+    /// [`fmt::Display`] impls (the same stable tags used in the pretty-printed output), not
+    /// `Debug`, so the JSON shape doesn't shift with unrelated field reordering.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        out.push_str("\"cat\":");
+        out.push_str(&json_string(&self.cat.to_string()));
+        out.push_str(",\"desc\":");
+        out.push_str(&json_string(&self.desc));
+        out.push_str(",\"traces\":[");
+        for (i, trace) in self.traces.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&trace.to_json());
+        }
+        out.push(']');
+        out.push_str(",\"help_msg\":");
+        match &self.help_msg {
+            Some(h) => out.push_str(&json_string(h)),
+            None => out.push_str("null"),
+        }
+        out.push('}');
+        out
+    }
+}
+
+impl ErrorTrace {
+    /// `loc` is rendered via [`Location`]'s [`fmt::Display`] impl rather than `Debug`, matching
+    /// [`Error::to_json`]'s rationale for `cat`.
+    fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        out.push_str("\"loc\":");
+        out.push_str(&json_string(&self.loc.to_string()));
+        out.push_str(",\"source\":");
+        out.push_str(&json_string(&self.source));
+        out.push_str(",\"desc\":");
+        match &self.desc {
+            Some(d) => out.push_str(&json_string(d)),
+            None => out.push_str("null"),
+        }
+        out.push_str(&format!(",\"start\":{},\"len\":{}", self.start, self.len));
+        out.push('}');
+        out
+    }
+}
+
 fn help_as_error(msg: &HelpMessage) -> Error {
     use fmt::Write;
 
@@ -226,7 +340,11 @@ where
     I: AsType + Into<Value> + 'static,
     S: Into<Arc<str>>,
 {
-    fscache::ensure_init(root); // initialise the cache
+    // Get-or-create the `FsCache` for this root. If `root` was already seen (e.g. an earlier
+    // `process_expression` call, or an explicit `configure_cache`), the existing
+    // instance -- and whatever configuration it already carries -- is reused as-is; the default
+    // here only seeds a *new* root's cache, so it never clobbers another caller's settings.
+    let cache = fscache::ensure_init(root, CacheConfig::default());
 
     let expr = parsing::expression(expr, loc, defs).map_err(|e| e.0)?;
     hir::handle_help(&expr, defs)?;
@@ -236,6 +354,7 @@ where
         root,
         wd,
         env: var::Environment::new(vars),
+        cache,
     };
     let output = evaluator.eval(seed.into(), cx)?.0;
 
@@ -249,6 +368,204 @@ pub use defs::{process_definition, recognise_definition};
 const ROWS_LIM: usize = 30;
 const COLS_LIM: usize = 7;
 
+/// The serialization format used by [`write_table`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableFormat {
+    /// A colourised, row/column-elided terminal grid. Equivalent to [`print_table`].
+    Pretty,
+    /// RFC-4180 CSV. The header row (if any) is driven by `table.header`.
+    Csv,
+    /// A JSON array of objects keyed by header name, or of arrays when headerless.
+    Json,
+    /// The [`kserd`] textual format.
+    Kserd,
+}
+
+/// Writes the [`Table`](::table::DataTable) to `wtr` in the given [`TableFormat`].
+///
+/// `Pretty` is identical to [`print_table`]. Every other format never elides rows/columns and
+/// never emits ANSI colour, making it suitable for piping into other tools or scripts.
+pub fn write_table(table: &Table, format: TableFormat, wtr: &mut dyn Write) -> io::Result<()> {
+    match format {
+        TableFormat::Pretty => print_table(table, wtr),
+        TableFormat::Csv => write_table_csv(table, wtr),
+        TableFormat::Json => write_table_json(table, wtr),
+        TableFormat::Kserd => write_table_kserd(table, wtr),
+    }
+}
+
+fn write_table_csv(table: &Table, wtr: &mut dyn Write) -> io::Result<()> {
+    let mut fmtr = Formatter::default();
+    let mut rows = table.rows();
+
+    if table.header {
+        if let Some(header) = rows.next() {
+            write_csv_row(header.map(|e| fmt_cell(e, &mut fmtr)), wtr)?;
+        }
+    }
+
+    for row in rows {
+        write_csv_row(row.map(|e| fmt_cell(e, &mut fmtr)), wtr)?;
+    }
+
+    Ok(())
+}
+
+fn write_csv_row<I: Iterator<Item = Str>>(row: I, wtr: &mut dyn Write) -> io::Result<()> {
+    let mut first = true;
+    for cell in row {
+        if !first {
+            write!(wtr, ",")?;
+        }
+        first = false;
+        write!(wtr, "{}", csv_quote(&cell))?;
+    }
+    writeln!(wtr)
+}
+
+/// Quotes a field per RFC-4180 if it contains a comma, quote, or newline.
+fn csv_quote(s: &str) -> String {
+    if s.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn write_table_json(table: &Table, wtr: &mut dyn Write) -> io::Result<()> {
+    let mut fmtr = Formatter::default();
+    let mut rows = table.rows();
+
+    let header: Vec<Str> = if table.header {
+        rows.next()
+            .map(|row| row.map(|e| fmt_cell(e, &mut fmtr)).collect())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    write!(wtr, "[")?;
+    let mut first_row = true;
+    for row in rows {
+        if !first_row {
+            write!(wtr, ",")?;
+        }
+        first_row = false;
+
+        let cells: Vec<String> = row.map(json_value).collect();
+
+        if header.is_empty() {
+            write!(wtr, "[")?;
+            for (i, cell) in cells.iter().enumerate() {
+                if i > 0 {
+                    write!(wtr, ",")?;
+                }
+                write!(wtr, "{}", cell)?;
+            }
+            write!(wtr, "]")?;
+        } else {
+            write!(wtr, "{{")?;
+            for (i, cell) in cells.iter().enumerate() {
+                if i > 0 {
+                    write!(wtr, ",")?;
+                }
+                let key = header.get(i).map(|h| h.as_str()).unwrap_or("");
+                write!(wtr, "{}:{}", json_string(key), cell)?;
+            }
+            write!(wtr, "}}")?;
+        }
+    }
+    writeln!(wtr, "]")
+}
+
+/// Formats a single cell as a JSON value, preserving numbers/bools as JSON literals rather than
+/// stringifying them like [`fmt_cell`] does for terminal display.
+fn json_value(entry: &Entry<Value>) -> String {
+    use Entry::*;
+    use Value as V;
+    match entry {
+        Nil | Obj(V::Nil) => "null".to_string(),
+        Num(n) | Obj(V::Num(n)) => {
+            let f = n.as_f64();
+            if f.is_finite() {
+                f.to_string()
+            } else {
+                "null".to_string()
+            }
+        }
+        Obj(V::Bool(b)) => b.to_string(),
+        Obj(V::Str(s)) => json_string(s),
+        Obj(V::Tab(t)) => json_string(&format!("<table [{},{}]>", t.rows_len(), t.cols_len())),
+        Obj(V::TabRow(_)) => json_string("<table row>"),
+        Obj(V::Ogma(x)) => json_string(&print_ogma_data(x.clone())),
+    }
+}
+
+/// Escapes a string as a JSON string literal, including the surrounding quotes.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn write_table_kserd(table: &Table, wtr: &mut dyn Write) -> io::Result<()> {
+    use kserd::Kserd;
+
+    let mut fmtr = Formatter::default();
+    let mut rows = table.rows();
+
+    let header: Vec<Str> = if table.header {
+        rows.next()
+            .map(|row| row.map(|e| fmt_cell(e, &mut fmtr)).collect())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let table_kserd = Kserd::new_seq(
+        rows.map(|row| {
+            let cells = row.map(kserd_value);
+            if header.is_empty() {
+                Kserd::new_seq(cells.collect())
+            } else {
+                Kserd::new_map(header.iter().map(|h| h.to_string()).zip(cells).collect())
+            }
+        })
+        .collect(),
+    );
+
+    writeln!(wtr, "{}", table_kserd.as_str())
+}
+
+/// Converts a single cell into a typed [`kserd::Kserd`] value, preserving numbers/bools rather
+/// than stringifying them like [`fmt_cell`] does for terminal display.
+fn kserd_value(entry: &Entry<Value>) -> kserd::Kserd {
+    use kserd::Kserd;
+    use Entry::*;
+    use Value as V;
+    match entry {
+        Nil | Obj(V::Nil) => Kserd::new_unit(),
+        Num(n) | Obj(V::Num(n)) => Kserd::new_num(n.as_f64()),
+        Obj(V::Bool(b)) => Kserd::new_bool(*b),
+        Obj(V::Str(s)) => Kserd::new_str(s.to_string()),
+        Obj(V::Tab(t)) => Kserd::new_str(format!("<table [{},{}]>", t.rows_len(), t.cols_len())),
+        Obj(V::TabRow(_)) => Kserd::new_str("<table row>".to_string()),
+        Obj(V::Ogma(x)) => Kserd::new_str(print_ogma_data(x.clone())),
+    }
+}
+
 /// Print the [`Table`](::table::DataTable) as a text formatted table to the given [`Write`]r.
 /// Colours the output. This is intended for terminal printing.
 pub fn print_table(table: &Table, wtr: &mut dyn Write) -> io::Result<()> {
@@ -316,6 +633,124 @@ pub fn print_table(table: &Table, wtr: &mut dyn Write) -> io::Result<()> {
     writeln!(wtr, "{}", out)
 }
 
+/// Distinguishes a directed from an undirected Graphviz document.
+///
+/// The kind controls both the opening keyword (`digraph`/`graph`) and the edge operator
+/// (`->` for a digraph, `--` for a graph).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphKind {
+    /// A directed graph, printed with `digraph` and `->` edges.
+    Digraph,
+    /// An undirected graph, printed with `graph` and `--` edges.
+    Graph,
+}
+
+impl GraphKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            GraphKind::Digraph => "digraph",
+            GraphKind::Graph => "graph",
+        }
+    }
+
+    fn edge_op(self) -> &'static str {
+        match self {
+            GraphKind::Digraph => "->",
+            GraphKind::Graph => "--",
+        }
+    }
+}
+
+/// Prints the [`Table`](::table::DataTable) as a Graphviz DOT document to the given [`Write`]r.
+///
+/// The table is interpreted as an edge list: if the table has a header, columns named `from`
+/// and `to` (case-insensitively) are used as the edge endpoints, otherwise the first two columns
+/// are used. Any remaining columns become edge attributes, keyed by their header name (or
+/// `attrN` when the table is headerless).
+pub fn print_graph(table: &Table, kind: GraphKind, wtr: &mut dyn Write) -> io::Result<()> {
+    let mut fmtr = Formatter::default();
+
+    let mut rows = table.rows();
+
+    let header: Vec<Str> = if table.header {
+        rows.next()
+            .map(|row| row.map(|e| fmt_cell(e, &mut fmtr)).collect())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let (from_idx, to_idx) = graph_endpoints(&header);
+
+    writeln!(wtr, "{} {{", kind.keyword())?;
+
+    for row in rows {
+        let cells: Vec<Str> = row.map(|e| fmt_cell(e, &mut fmtr)).collect();
+        if from_idx >= cells.len() || to_idx >= cells.len() {
+            continue;
+        }
+
+        write!(
+            wtr,
+            "  {} {} {}",
+            dot_id(&cells[from_idx]),
+            kind.edge_op(),
+            dot_id(&cells[to_idx])
+        )?;
+
+        let mut attrs = cells
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != from_idx && i != to_idx)
+            .peekable();
+
+        if attrs.peek().is_some() {
+            write!(wtr, " [")?;
+            let mut first = true;
+            for (i, value) in attrs {
+                if !first {
+                    write!(wtr, ", ")?;
+                }
+                first = false;
+                match header.get(i) {
+                    Some(name) => write!(wtr, "{}={}", name, dot_id(value))?,
+                    None => write!(wtr, "attr{}={}", i, dot_id(value))?,
+                }
+            }
+            write!(wtr, "]")?;
+        }
+
+        writeln!(wtr, ";")?;
+    }
+
+    writeln!(wtr, "}}")
+}
+
+/// Works out which two columns are the edge endpoints, preferring `from`/`to` header names.
+fn graph_endpoints(header: &[Str]) -> (usize, usize) {
+    let from = header.iter().position(|h| h.eq_ignore_ascii_case("from"));
+    let to = header.iter().position(|h| h.eq_ignore_ascii_case("to"));
+    match (from, to) {
+        (Some(from), Some(to)) => (from, to),
+        _ => (0, 1),
+    }
+}
+
+/// Quotes and escapes a string for use as a DOT node identifier or attribute value.
+fn dot_id(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
 /// Prints the processing error. Uses colour and assumes printing is to the terminal.
 /// Use [`Error::print`] if this is not the case.
 pub fn print_error(err: &Error, wtr: &mut dyn Write) -> io::Result<()> {
@@ -377,34 +812,103 @@ pub fn print_ogma_data(data: types::OgmaData) -> String {
 }
 
 // ###### CACHING ##############################################################
-::lazy_static::lazy_static! {
-    static ref FSCACHE: fscache::FsCache = Default::default();
+
+/// Configuration for the filesystem cache used by commands such as `open`.
+///
+/// Each canonicalized root directory gets its own [`FsCache`](fscache::FsCache) instance --
+/// there is no single process-wide cache -- so two `process_expression` calls against different
+/// roots (e.g. two tests, each pointed at its own temp directory) never share state or race over
+/// configuration. The resolved cache for a `process_expression` call's `root` is carried for the
+/// rest of evaluation via `hir::Context::cache`. Call [`configure_cache`] before evaluating
+/// anything under a given root to tune or fully disable caching and file-watching for it --
+/// important for short-lived or test invocations where spawning background watcher/cleaner
+/// threads and stalling reads is wasteful.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CacheConfig {
+    /// How long a cached value is kept before it is considered expired.
+    pub lifespan: std::time::Duration,
+    /// The file-watcher debounce window.
+    pub debounce: std::time::Duration,
+    /// Whether caching and file-watching are enabled at all.
+    pub enabled: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            lifespan: std::time::Duration::from_secs(60 * 3), // 3 minutes
+            debounce: std::time::Duration::from_millis(5),    // 5ms fs watching
+            enabled: true,
+        }
+    }
+}
+
+/// Drops any cached value for `path` under `root`'s cache, across all cached types.
+///
+/// Useful for callers that already know a file changed and don't want to wait on the debounced
+/// notify watcher. A no-op if `root` has no cache yet.
+pub fn invalidate_cache(root: &Path, path: &Path) {
+    if let Some(cache) = fscache::lookup(root) {
+        cache.invalidate(path);
+    }
+}
+
+/// Clears `root`'s cache entirely. A no-op if `root` has no cache yet.
+pub fn clear_cache(root: &Path) {
+    if let Some(cache) = fscache::lookup(root) {
+        cache.clear();
+    }
+}
+
+/// Sets the [`CacheConfig`] for `root`'s cache, creating it (without spawning its background
+/// threads more than once) if this is the first call for `root`.
+///
+/// `enabled` applies immediately to every future `get`/`insert` on this cache. `lifespan` is read
+/// by the cleaner thread before each of its waits, so a new value is used from the sweep after
+/// the one already pending. `debounce` takes effect immediately for the read-path settle check
+/// (see [`FsCache::get`](fscache::FsCache::get)), but the file-watcher thread's own coalescing
+/// window is fixed at whatever `debounce` was in effect the first time this root's cache was
+/// created -- changing it later does not retune an already-running watcher. A cache first
+/// created with `enabled: false` has no cleaner or watcher threads, and enabling it later does
+/// not start them.
+pub fn configure_cache(root: &Path, config: CacheConfig) {
+    fscache::ensure_init(root, config).configure(config);
+}
+
+/// Returns `root`'s current [`CacheConfig`], or `None` if `root` has no cache yet.
+pub fn cache_config(root: &Path) -> Option<CacheConfig> {
+    fscache::lookup(root).map(|cache| cache.config())
 }
 
 mod fscache {
-    use super::FSCACHE;
-    use super::{HashMap, HashSet, Mutex};
+    use super::{CacheConfig, HashMap, HashSet, Mutex};
     use crate::types::{AsType, Type};
-    use ::libs::parking_lot::Once;
     use std::{
         convert::TryFrom,
         error,
         path::{Path, PathBuf},
+        sync::Arc,
         time::{Duration, Instant},
     };
 
-    const LIFESPAN: Duration = Duration::from_secs(60 * 3); // 3 minutes
-    const DEBOUNCE: Duration = Duration::from_millis(5); // 5ms fs watching
-    static INIT: Once = Once::new();
+    ::lazy_static::lazy_static! {
+        /// One [`FsCache`] per canonicalized root that's been seen by [`ensure_init`].
+        static ref CACHES: Mutex<HashMap<PathBuf, Arc<FsCache>>> = Default::default();
+    }
 
     #[derive(PartialEq, Eq, Hash)]
     struct Key(String, Type);
     type Value = (Instant, crate::types::Value);
     type Map = HashMap<Key, Value>;
 
-    #[derive(Default)]
+    /// A cache of [`AsType`] values read from the filesystem, scoped to a single root.
     pub struct FsCache {
         map: Mutex<Map>,
+        config: Mutex<CacheConfig>,
+        /// Set the instant a raw (undebounced) filesystem event is observed under this cache's
+        /// root, so `get` only stalls while a change may still be in flight, rather than
+        /// sleeping on every read. See [`watch_fs_raw`].
+        last_change: Mutex<Option<Instant>>,
     }
 
     impl Key {
@@ -414,14 +918,25 @@ mod fscache {
     }
 
     impl FsCache {
-        /// This can be called multiple times, and will only initialise on the first call.
+        fn new(config: CacheConfig) -> Self {
+            FsCache {
+                map: Mutex::new(Map::default()),
+                config: Mutex::new(config),
+                last_change: Mutex::new(None),
+            }
+        }
 
         pub fn get<T>(&self, path: &Path) -> Option<T>
         where
             T: AsType,
             T: TryFrom<crate::types::Value>,
         {
-            std::thread::sleep(DEBOUNCE * 5); // we sleep for the 5 x debounce duration to give time for the fs watcher to catch up
+            let config = self.config();
+            if !config.enabled {
+                return None;
+            }
+
+            self.await_settled(config.debounce);
 
             let key = Key::from::<T>(path);
             let mut lock = self.map.lock();
@@ -438,6 +953,10 @@ mod fscache {
             T: AsType,
             T: Into<crate::types::Value>,
         {
+            if !self.config().enabled {
+                return;
+            }
+
             let key = Key::from::<T>(path);
             self.map.lock().insert(key, (Instant::now(), value.into()));
         }
@@ -456,42 +975,127 @@ mod fscache {
                 self.map.lock().retain(|k, _| !paths.contains(&k.0));
             }
         }
+
+        /// Drops any cached value for `path`, across all cached types.
+        pub fn invalidate(&self, path: &Path) {
+            let p = path_to_str(path);
+            self.map.lock().retain(|k, _| k.0 != p);
+        }
+
+        /// Clears the cache entirely.
+        pub fn clear(&self) {
+            self.map.lock().clear();
+        }
+
+        /// Updates the cache's configuration. See [`super::configure_cache`] for what takes
+        /// effect immediately versus what's frozen once the cache's background threads exist.
+        pub fn configure(&self, config: CacheConfig) {
+            *self.config.lock() = config;
+        }
+
+        /// Returns the cache's current configuration.
+        pub fn config(&self) -> CacheConfig {
+            *self.config.lock()
+        }
+
+        /// Marks that a filesystem change may be in flight, starting the settle window from now.
+        fn mark_changed(&self) {
+            *self.last_change.lock() = Some(Instant::now());
+        }
+
+        /// Sleeps only if a filesystem change was observed within the last `debounce * 5`
+        /// window, for just long enough that the debounced watcher has had a chance to apply it.
+        fn await_settled(&self, debounce: Duration) {
+            let settle = debounce * 5;
+            let Some(last_change) = *self.last_change.lock() else {
+                return;
+            };
+            let elapsed = last_change.elapsed();
+            if elapsed < settle {
+                std::thread::sleep(settle - elapsed);
+            }
+        }
     }
 
-    pub fn ensure_init(root: &Path) {
+    /// Returns `root`'s cache, creating it -- and spawning its cleaner/watcher threads, if
+    /// `config.enabled` -- on the first call for that root. Later calls for the same root return
+    /// the existing instance unchanged; `config` is only ever used to seed a *new* root's cache,
+    /// so it can never clobber a root that's already been configured.
+    pub fn ensure_init(root: &Path, config: CacheConfig) -> Arc<FsCache> {
         let canon_root = root
             .canonicalize()
             .expect("must be able to canonicalize root");
 
-        INIT.call_once(|| {
-            std::thread::Builder::new()
-                .name("ogma-fs-cache-cleaner".to_string())
-                .spawn(clean_opened_cache_periodically)
-                .unwrap();
-            std::thread::Builder::new()
-                .name("ogma-fs-watcher".to_string())
-                .spawn(|| watch_fs(canon_root).expect("failed to start fs watcher"))
-                .unwrap();
-        });
+        let mut caches = CACHES.lock();
+        if let Some(cache) = caches.get(&canon_root) {
+            return Arc::clone(cache);
+        }
+
+        let cache = Arc::new(FsCache::new(config));
+
+        if config.enabled {
+            spawn_cleaner(Arc::clone(&cache));
+            spawn_watchers(Arc::clone(&cache), canon_root.clone(), config.debounce);
+        }
+
+        caches.insert(canon_root, Arc::clone(&cache));
+        cache
+    }
+
+    /// Looks up `root`'s cache without creating one, returning `None` if `root` hasn't been
+    /// passed to [`ensure_init`] yet.
+    pub fn lookup(root: &Path) -> Option<Arc<FsCache>> {
+        let canon_root = root.canonicalize().ok()?;
+        CACHES.lock().get(&canon_root).cloned()
     }
 
     fn path_to_str(path: &Path) -> String {
         path.display().to_string().to_lowercase()
     }
 
-    pub fn clean_opened_cache_periodically() {
-        loop {
-            std::thread::sleep(LIFESPAN);
-            FSCACHE.remove_expired(LIFESPAN);
-        }
+    fn spawn_cleaner(cache: Arc<FsCache>) {
+        std::thread::Builder::new()
+            .name("ogma-fs-cache-cleaner".to_string())
+            .spawn(move || loop {
+                let lifespan = cache.config().lifespan;
+                std::thread::sleep(lifespan);
+                cache.remove_expired(lifespan);
+            })
+            .unwrap();
     }
 
-    pub fn watch_fs(canon_root: PathBuf) -> Result<(), Box<dyn error::Error>> {
+    fn spawn_watchers(cache: Arc<FsCache>, canon_root: PathBuf, debounce: Duration) {
+        std::thread::Builder::new()
+            .name("ogma-fs-watcher".to_string())
+            .spawn({
+                let cache = Arc::clone(&cache);
+                let canon_root = canon_root.clone();
+                move || watch_fs(cache, canon_root, debounce).expect("failed to start fs watcher")
+            })
+            .unwrap();
+
+        std::thread::Builder::new()
+            .name("ogma-fs-watcher-raw".to_string())
+            .spawn(move || {
+                watch_fs_raw(cache, canon_root).expect("failed to start raw fs watcher")
+            })
+            .unwrap();
+    }
+
+    /// Coalesces filesystem events over `debounce` and applies them to `cache` -- this is the
+    /// path that actually invalidates stale entries, but only once notify has finished debouncing
+    /// (see [`watch_fs_raw`] for the earlier, immediate dirty-marking this cache's `get` relies
+    /// on to avoid serving stale data in the meantime).
+    fn watch_fs(
+        cache: Arc<FsCache>,
+        canon_root: PathBuf,
+        debounce: Duration,
+    ) -> Result<(), Box<dyn error::Error>> {
         use ::notify::{DebouncedEvent::*, *};
 
         // create the mpsc channel to communicate with the file watcher
         let (wsx, wrx) = std::sync::mpsc::channel();
-        let mut watcher = notify::watcher(wsx, DEBOUNCE)
+        let mut watcher = notify::watcher(wsx, debounce)
             .map_err(|e| format!("failed to setup watcher: {}", e))?;
 
         // spawn a new thread in which we look for events
@@ -499,7 +1103,7 @@ mod fscache {
 
         let mut set = HashSet::default();
         loop {
-            std::thread::sleep(DEBOUNCE);
+            std::thread::sleep(debounce);
             set.clear();
             for ev in wrx.try_iter() {
                 match ev {
@@ -517,7 +1121,27 @@ mod fscache {
             let drain = set
                 .drain()
                 .map(|x| x.strip_prefix(&canon_root).unwrap().to_path_buf());
-            FSCACHE.remove_path_changes(drain);
+            cache.remove_path_changes(drain);
         }
     }
+
+    /// Marks `cache` dirty the instant any raw (undebounced) filesystem event is observed under
+    /// `canon_root`, closing the window where `get` would otherwise serve a value that's gone
+    /// stale the moment a write landed but before [`watch_fs`]'s debounced pass has caught up.
+    fn watch_fs_raw(cache: Arc<FsCache>, canon_root: PathBuf) -> Result<(), Box<dyn error::Error>> {
+        use ::notify::*;
+
+        let (rsx, rrx) = std::sync::mpsc::channel();
+        let mut watcher =
+            notify::raw_watcher(rsx).map_err(|e| format!("failed to setup raw watcher: {}", e))?;
+        watcher
+            .watch(&canon_root, RecursiveMode::Recursive)
+            .map_err(|e| format!("failed to watch root: {}", e))?;
+
+        for _event in rrx.iter() {
+            cache.mark_changed();
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,270 @@
+//! Unit tests for the pure helper functions and small public APIs in `lib.rs`.
+
+use super::*;
+
+#[test]
+fn dot_id_quotes_plain_strings() {
+    assert_eq!(dot_id("plain"), "\"plain\"");
+}
+
+#[test]
+fn dot_id_escapes_quotes_and_backslashes() {
+    assert_eq!(
+        dot_id("has \"quote\" and \\backslash"),
+        "\"has \\\"quote\\\" and \\\\backslash\""
+    );
+}
+
+#[test]
+fn graph_endpoints_prefers_named_from_to_columns() {
+    let header: Vec<Str> = vec![Str::from("weight"), Str::from("to"), Str::from("from")];
+    assert_eq!(graph_endpoints(&header), (2, 1));
+}
+
+#[test]
+fn graph_endpoints_falls_back_to_first_two_columns() {
+    let header: Vec<Str> = vec![Str::from("a"), Str::from("b"), Str::from("c")];
+    assert_eq!(graph_endpoints(&header), (0, 1));
+    assert_eq!(graph_endpoints(&[]), (0, 1));
+}
+
+#[test]
+fn csv_quote_leaves_plain_fields_untouched() {
+    assert_eq!(csv_quote("plain"), "plain");
+}
+
+#[test]
+fn csv_quote_quotes_fields_with_special_characters() {
+    assert_eq!(csv_quote("has,comma"), "\"has,comma\"");
+    assert_eq!(csv_quote("has \"quote\""), "\"has \"\"quote\"\"\"");
+    assert_eq!(csv_quote("has\nnewline"), "\"has\nnewline\"");
+}
+
+#[test]
+fn json_string_escapes_control_characters() {
+    assert_eq!(json_string("plain"), "\"plain\"");
+    assert_eq!(json_string("a\"b"), "\"a\\\"b\"");
+    assert_eq!(json_string("a\nb"), "\"a\\nb\"");
+    assert_eq!(json_string("a\tb"), "\"a\\tb\"");
+}
+
+#[test]
+fn error_to_json_exposes_traces_and_help_msg_as_structured_fields() {
+    let err = Error {
+        cat: crate::err::Category::Help,
+        desc: "desc".to_string(),
+        traces: vec![ErrorTrace {
+            loc: Location::Shell,
+            source: "src".to_string(),
+            desc: Some("trace desc".to_string()),
+            start: 1,
+            len: 2,
+        }],
+        help_msg: Some("help".to_string()),
+    };
+
+    let json = err.to_json();
+
+    assert!(json.contains("\"desc\":\"desc\""));
+    assert!(json.contains("\"source\":\"src\""));
+    assert!(json.contains("\"start\":1"));
+    assert!(json.contains("\"len\":2"));
+    assert!(json.contains("\"help_msg\":\"help\""));
+}
+
+#[test]
+fn error_to_json_emits_null_for_missing_trace_desc_and_help_msg() {
+    let err = Error {
+        cat: crate::err::Category::Help,
+        desc: "desc".to_string(),
+        traces: vec![ErrorTrace {
+            loc: Location::Shell,
+            source: "src".to_string(),
+            desc: None,
+            start: 0,
+            len: 0,
+        }],
+        help_msg: None,
+    };
+
+    let json = err.to_json();
+
+    assert!(json.contains("\"desc\":null"));
+    assert!(json.ends_with("\"help_msg\":null}"));
+}
+
+/// Creates a fresh, unique temp directory for a cache test to use as its root -- each test needs
+/// its own root since caches are keyed by canonicalized root path and, once created, never go
+/// away for the lifetime of the process.
+fn temp_cache_root(name: &str) -> std::path::PathBuf {
+    static COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("ogma-test-{}-{}-{}", name, std::process::id(), n));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn configure_cache_is_not_overridden_by_later_ensure_init_defaults() {
+    let root = temp_cache_root("no-clobber");
+    let custom = CacheConfig {
+        enabled: false,
+        ..CacheConfig::default()
+    };
+    configure_cache(&root, custom);
+
+    // `process_expression`'s entry point always calls `fscache::ensure_init` with the
+    // library's default config; that must not clobber an explicitly configured root's config.
+    crate::fscache::ensure_init(&root, CacheConfig::default());
+
+    assert!(!cache_config(&root).unwrap().enabled);
+}
+
+#[test]
+fn caches_for_different_roots_do_not_share_or_clobber_configuration() {
+    // Two tests (or two `process_expression` callers) pointed at different roots must never
+    // race over a shared config, since each root gets its own independent `FsCache`.
+    let root_a = temp_cache_root("root-a");
+    let root_b = temp_cache_root("root-b");
+
+    configure_cache(
+        &root_a,
+        CacheConfig {
+            enabled: false,
+            ..CacheConfig::default()
+        },
+    );
+    configure_cache(&root_b, CacheConfig::default());
+
+    assert!(!cache_config(&root_a).unwrap().enabled);
+    assert!(cache_config(&root_b).unwrap().enabled);
+}
+
+#[test]
+fn cache_config_is_none_for_an_unseen_root() {
+    let root = temp_cache_root("unseen");
+    assert!(cache_config(&root).is_none());
+}
+
+/// Builds a small headered [`Table`] out of string cells, for end-to-end tests of the printing
+/// and writing functions below.
+fn str_table(rows: Vec<Vec<&str>>) -> Table {
+    let rows: Vec<Vec<Entry<Value>>> = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| Entry::Obj(Value::Str(Str::from(cell))))
+                .collect()
+        })
+        .collect();
+    let mut table = Table::from(rows);
+    table.header = true;
+    table
+}
+
+#[test]
+fn print_graph_emits_edges_attrs_and_headerless_fallback() {
+    let table = str_table(vec![
+        vec!["from", "to", "weight"],
+        vec!["a", "b", "1"],
+        vec!["b", "c", "2"],
+    ]);
+
+    let mut buf = Vec::new();
+    print_graph(&table, GraphKind::Digraph, &mut buf).unwrap();
+    let dot = String::from_utf8(buf).unwrap();
+
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("\"a\" -> \"b\" [weight=\"1\"];\n"));
+    assert!(dot.contains("\"b\" -> \"c\" [weight=\"2\"];\n"));
+
+    // Headerless: falls back to the first two columns as endpoints, and remaining columns are
+    // named `attrN` rather than by a (nonexistent) header.
+    let mut headerless = str_table(vec![vec!["a", "b", "1"]]);
+    headerless.header = false;
+
+    let mut buf = Vec::new();
+    print_graph(&headerless, GraphKind::Graph, &mut buf).unwrap();
+    let dot = String::from_utf8(buf).unwrap();
+
+    assert!(dot.starts_with("graph {\n"));
+    assert!(dot.contains("\"a\" -- \"b\" [attr2=\"1\"];\n"));
+}
+
+#[test]
+fn write_table_csv_writes_header_and_quoted_rows_without_eliding() {
+    let table = str_table(vec![
+        vec!["a", "b"],
+        vec!["1", "has,comma"],
+        vec!["2", "plain"],
+    ]);
+
+    let mut buf = Vec::new();
+    write_table_csv(&table, &mut buf).unwrap();
+    let csv = String::from_utf8(buf).unwrap();
+
+    assert_eq!(csv, "a,b\n1,\"has,comma\"\n2,plain\n");
+}
+
+#[test]
+fn write_table_json_emits_objects_for_a_headered_table() {
+    let table = str_table(vec![vec!["a", "b"], vec!["1", "x"], vec!["2", "y"]]);
+
+    let mut buf = Vec::new();
+    write_table_json(&table, &mut buf).unwrap();
+    let json = String::from_utf8(buf).unwrap();
+
+    assert_eq!(json, "[{\"a\":\"1\",\"b\":\"x\"},{\"a\":\"2\",\"b\":\"y\"}]\n");
+}
+
+#[test]
+fn write_table_json_emits_arrays_for_a_headerless_table() {
+    let mut table = str_table(vec![vec!["1", "x"], vec!["2", "y"]]);
+    table.header = false;
+
+    let mut buf = Vec::new();
+    write_table_json(&table, &mut buf).unwrap();
+    let json = String::from_utf8(buf).unwrap();
+
+    assert_eq!(json, "[[\"1\",\"x\"],[\"2\",\"y\"]]\n");
+}
+
+#[test]
+fn write_table_kserd_emits_maps_keyed_by_header() {
+    let table = str_table(vec![vec!["a", "b"], vec!["1", "x"]]);
+
+    let mut buf = Vec::new();
+    write_table_kserd(&table, &mut buf).unwrap();
+    let kserd = String::from_utf8(buf).unwrap();
+
+    assert!(kserd.contains("a"));
+    assert!(kserd.contains("\"1\""));
+    assert!(kserd.contains("b"));
+    assert!(kserd.contains("\"x\""));
+}
+
+#[test]
+fn help_message_to_structured_maps_required_optional_and_custom_params_and_drops_break() {
+    let mut msg = HelpMessage::new("cmd");
+    msg.desc = Str::from("desc");
+    msg.params = vec![
+        HelpParameter::Required(Str::from("req")),
+        HelpParameter::Break,
+        HelpParameter::Optional(Str::from("opt")),
+        HelpParameter::Custom(Str::from("custom")),
+    ];
+
+    let structured = msg.to_structured();
+
+    assert_eq!(structured.command, "cmd");
+    assert_eq!(structured.description, "desc");
+    assert_eq!(
+        structured.parameters,
+        vec![
+            HelpParamKind::Required("req".to_string()),
+            HelpParamKind::Optional("opt".to_string()),
+            HelpParamKind::Custom("custom".to_string()),
+        ]
+    );
+}